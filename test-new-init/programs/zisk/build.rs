@@ -1,14 +1,311 @@
 use std::env;
-use std::fs;
-use std::path::Path;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
 
 fn main() {
-    // Tell cargo to rerun this script if input files change
-    println!("cargo:rerun-if-changed=inputs/");
-    
     // Create build directory if it doesn't exist
     let build_dir = Path::new("build");
     if !build_dir.exists() {
         fs::create_dir(build_dir).unwrap();
     }
+
+    track_inputs(build_dir);
+    stamp_provenance();
+    generate_linker_script();
+    build_guest_elf(build_dir);
+    detect_backends(build_dir);
+    bundle_dashboard();
+}
+
+/// Recursively walks `inputs/`, telling Cargo to rerun the build if any file
+/// under it changes (a plain `cargo:rerun-if-changed=inputs/` does not expand
+/// to nested files) and writes `build/inputs.manifest`: one
+/// `path\tsize\thash` line per input. A later `verify` step can diff this
+/// manifest against a proof's recorded inputs to catch stale or swapped
+/// input bytes.
+fn track_inputs(build_dir: &Path) {
+    let inputs_dir = Path::new("inputs");
+    if !inputs_dir.exists() {
+        return;
+    }
+
+    let mut manifest = String::new();
+    let mut stack = vec![inputs_dir.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                stack.push(path);
+                continue;
+            }
+
+            println!("cargo:rerun-if-changed={}", path.display());
+            let (size, hash) = hash_input(&path);
+            let rel = path.strip_prefix(inputs_dir).unwrap_or(&path);
+            manifest.push_str(&format!("{}\t{}\t{:016x}\n", rel.display(), size, hash));
+        }
+    }
+
+    fs::write(build_dir.join("inputs.manifest"), manifest).unwrap();
+}
+
+/// Returns `(size_in_bytes, content_hash)` for a single input file. FNV-1a
+/// (not `DefaultHasher`, whose algorithm may change across std versions) so
+/// the manifest is stable for comparison across builds.
+fn hash_input(path: &Path) -> (u64, u64) {
+    let mut file = File::open(path).unwrap();
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).unwrap();
+
+    (buf.len() as u64, fnv1a(&buf))
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    let mut hash = OFFSET_BASIS;
+    for &byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(PRIME);
+    }
+    hash
+}
+
+// Exports ZISK_BUILD_* env!() values read by `zisk version --verbose` and
+// `provenance::BuildProvenance`, which stamps them into every proof.
+fn stamp_provenance() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string());
+
+    // `git -C` walks up from manifest_dir to find the repo's real .git,
+    // wherever it lives relative to this crate.
+    if let Some(git_dir) = run_git(&manifest_dir, &["rev-parse", "--absolute-git-dir"]) {
+        println!("cargo:rerun-if-changed={}", Path::new(&git_dir).join("HEAD").display());
+
+        let commit = run_git(&manifest_dir, &["rev-parse", "HEAD"]).unwrap_or_else(|| "unknown".to_string());
+        let dirty = run_git(&manifest_dir, &["status", "--porcelain"])
+            .map(|out| !out.trim().is_empty())
+            .unwrap_or(false);
+
+        println!("cargo:rustc-env=ZISK_BUILD_COMMIT={commit}");
+        println!("cargo:rustc-env=ZISK_BUILD_DIRTY={dirty}");
+    } else {
+        println!("cargo:rustc-env=ZISK_BUILD_COMMIT=unknown");
+        println!("cargo:rustc-env=ZISK_BUILD_DIRTY=false");
+    }
+
+    let host = env::var("HOST").unwrap_or_else(|_| "unknown".to_string());
+    let target = env::var("TARGET").unwrap_or_else(|_| "unknown".to_string());
+    println!("cargo:rustc-env=ZISK_BUILD_HOST={host}");
+    println!("cargo:rustc-env=ZISK_BUILD_TARGET={target}");
+
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version = Command::new(&rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .map(|s| s.trim().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=ZISK_BUILD_RUSTC={rustc_version}");
+
+    // So a channel switch (stable/beta/nightly) invalidates the cached
+    // provenance instead of silently stamping proofs with stale toolchain info.
+    println!("cargo:rerun-if-env-changed=CFG_RELEASE_CHANNEL");
+}
+
+fn run_git(dir: &str, args: &[&str]) -> Option<String> {
+    Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .map(|s| s.trim().to_string())
+}
+
+// Writes the zkVM linker script to OUT_DIR. Override with ZISK_LINKER_SCRIPT.
+fn generate_linker_script() {
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+
+    let contents = match env::var_os("ZISK_LINKER_SCRIPT") {
+        Some(custom) => {
+            println!("cargo:rerun-if-changed={}", Path::new(&custom).display());
+            fs::read_to_string(&custom).expect("failed to read ZISK_LINKER_SCRIPT")
+        }
+        // Nothing on disk backs the default script, so there's no source
+        // path to hand to rerun-if-changed (a missing path means "always
+        // rerun", which would defeat incremental builds for the crate).
+        None => default_linker_script(),
+    };
+
+    fs::write(out_dir.join("zisk-link.x"), contents).unwrap();
+    println!("cargo:rustc-link-search={}", out_dir.display());
+}
+
+fn default_linker_script() -> String {
+    r#"/* Auto-generated by build.rs: ZisK RISC-V zkVM memory layout */
+MEMORY
+{
+    ROM    (rx)  : ORIGIN = 0x80000000, LENGTH = 0x00100000
+    RAM    (rwx) : ORIGIN = 0x80100000, LENGTH = 0x01000000
+    INPUT  (r)   : ORIGIN = 0x90000000, LENGTH = 0x00100000
+    OUTPUT (rw)  : ORIGIN = 0x90100000, LENGTH = 0x00010000
+}
+
+SECTIONS
+{
+    .text   : { *(.text .text.*) }     > ROM
+    .rodata : { *(.rodata .rodata.*) } > ROM
+    .data   : { *(.data .data.*) }     > RAM
+    .bss    : { *(.bss .bss.*) }       > RAM
+    .input  : { *(.input) }            > INPUT
+    .output : { *(.output) }           > OUTPUT
+}
+"#
+    .to_string()
+}
+
+// Cross-compiles guest/ to the zkVM target and exposes env!("ZISK_GUEST_ELF").
+// Opt in with ZISK_BUILD_GUEST=1; override the toolchain with ZISK_CARGO/ZISK_CC.
+fn build_guest_elf(build_dir: &Path) {
+    if env::var_os("ZISK_BUILD_GUEST").is_none() {
+        return;
+    }
+
+    let guest_dir = Path::new("guest");
+    if !guest_dir.exists() {
+        return;
+    }
+    rerun_if_changed_recursive(guest_dir);
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let target_dir = out_dir.join("guest-target");
+
+    let cargo = env::var("ZISK_CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let mut cmd = Command::new(&cargo);
+    cmd.args(["build", "--release", "--target", "riscv64ima-zisk-zkvm-elf"])
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .current_dir(guest_dir);
+    if let Ok(cc) = env::var("ZISK_CC") {
+        cmd.env("CC_riscv64ima_zisk_zkvm_elf", cc);
+    }
+
+    let status = cmd.status().expect("failed to invoke guest toolchain");
+    assert!(status.success(), "guest build failed");
+
+    let elf = find_elf(&target_dir).expect("guest build produced no .elf");
+    let dest = build_dir.join("guest.elf");
+    fs::copy(&elf, &dest).unwrap();
+    println!("cargo:rustc-env=ZISK_GUEST_ELF={}", dest.display());
+}
+
+// Tells Cargo to rerun the build if any file under `dir` changes.
+fn rerun_if_changed_recursive(dir: &Path) {
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                stack.push(path);
+            } else {
+                println!("cargo:rerun-if-changed={}", path.display());
+            }
+        }
+    }
+}
+
+/// Recursively searches `dir` for the first `.elf` file it finds.
+fn find_elf(dir: &Path) -> Option<PathBuf> {
+    let mut stack = vec![dir.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                stack.push(path);
+            } else if path.extension().is_some_and(|ext| ext == "elf") {
+                return Some(path);
+            }
+        }
+    }
+    None
+}
+
+// Probes for available proving acceleration (CUDA/Metal/OpenCL libraries)
+// and turns each one found into a cargo:rustc-cfg=zisk_backend_{name}, so the
+// crate can conditionally compile GPU prover paths. Re-probed every build
+// (cheap stat calls) so installing/removing an SDK takes effect immediately;
+// build/backends.cfg is kept only for the CLI to read back and print which
+// backends were compiled in.
+fn detect_backends(build_dir: &Path) {
+    println!("cargo:rustc-check-cfg=cfg(zisk_backend_cpu, zisk_backend_cuda, zisk_backend_metal, zisk_backend_opencl)");
+
+    let backends = probe_backends();
+    fs::write(build_dir.join("backends.cfg"), backends.join("\n")).unwrap();
+
+    for backend in &backends {
+        println!("cargo:rustc-cfg=zisk_backend_{backend}");
+    }
+}
+
+fn probe_backends() -> Vec<String> {
+    let mut backends = vec!["cpu".to_string()];
+
+    let cuda_libs = [
+        "/usr/local/cuda/lib64/libcudart.so",
+        "/usr/lib/x86_64-linux-gnu/libcudart.so",
+    ];
+    if cuda_libs.iter().any(|lib| Path::new(lib).exists()) {
+        backends.push("cuda".to_string());
+    }
+
+    if cfg!(target_os = "macos") && Path::new("/System/Library/Frameworks/Metal.framework").exists() {
+        backends.push("metal".to_string());
+    }
+
+    let opencl_libs = ["/usr/lib/x86_64-linux-gnu/libOpenCL.so", "/usr/lib/libOpenCL.so"];
+    if opencl_libs.iter().any(|lib| Path::new(lib).exists()) {
+        backends.push("opencl".to_string());
+    }
+
+    backends
+}
+
+// Embeds frontend/dist into the binary for `zisk serve`. Drops placeholder
+// stubs when the real frontend hasn't been built yet so `cargo check` passes.
+fn bundle_dashboard() {
+    let dist_dir = Path::new("frontend/dist");
+    println!("cargo:rerun-if-changed=frontend/dist");
+
+    if !dist_dir.exists() {
+        fs::create_dir_all(dist_dir).unwrap();
+    }
+    for stub in ["index.html", "bundle.js", "bundle.wasm"] {
+        let path = dist_dir.join(stub);
+        if !path.exists() {
+            fs::write(&path, b"").unwrap();
+        }
+    }
+
+    static_files::resource_dir(dist_dir)
+        .build()
+        .expect("failed to embed frontend/dist into the binary");
 }
@@ -0,0 +1,17 @@
+// Reports which zisk_backend_* cfgs build.rs's detect_backends() compiled in.
+pub fn compiled() -> Vec<&'static str> {
+    let mut backends = Vec::new();
+    if cfg!(zisk_backend_cpu) {
+        backends.push("cpu");
+    }
+    if cfg!(zisk_backend_cuda) {
+        backends.push("cuda");
+    }
+    if cfg!(zisk_backend_metal) {
+        backends.push("metal");
+    }
+    if cfg!(zisk_backend_opencl) {
+        backends.push("opencl");
+    }
+    backends
+}
@@ -0,0 +1,65 @@
+mod backends;
+mod provenance;
+mod serve;
+
+use provenance::BuildProvenance;
+use std::env;
+use std::path::Path;
+
+fn main() {
+    let mut args = env::args().skip(1);
+    match args.next().as_deref() {
+        Some("version") => {
+            let verbose = args.any(|a| a == "--verbose" || a == "-v");
+            print_version(verbose);
+        }
+        Some("prove") => {
+            let proof_dir = args.next().unwrap_or_else(|| "build/proof".to_string());
+            prove(Path::new(&proof_dir));
+        }
+        Some("serve") => {
+            let port = args.next().and_then(|p| p.parse().ok()).unwrap_or(8080);
+            serve::run(port);
+        }
+        other => {
+            eprintln!("usage: zisk <version [--verbose] | prove <dir> | serve [port]>");
+            if let Some(cmd) = other {
+                eprintln!("unknown subcommand: {cmd}");
+            }
+            std::process::exit(1);
+        }
+    }
+}
+
+// Stand-in for the real proving pipeline: loads the guest ELF build.rs
+// compiled (see build_guest_elf) and stamps the provenance header alongside
+// the proof so it can be traced back to the build that made it.
+fn prove(proof_dir: &Path) {
+    match option_env!("ZISK_GUEST_ELF") {
+        Some(guest_elf) => println!("guest: {guest_elf}"),
+        None => panic!("no guest ELF was built; rerun with ZISK_BUILD_GUEST=1 set"),
+    }
+
+    std::fs::create_dir_all(proof_dir).expect("failed to create proof directory");
+    BuildProvenance::current()
+        .write_header(proof_dir)
+        .expect("failed to write proof provenance header");
+}
+
+fn print_version(verbose: bool) {
+    println!("zisk {}", env!("CARGO_PKG_VERSION"));
+    if !verbose {
+        return;
+    }
+
+    let prov = BuildProvenance::current();
+    println!(
+        "commit:  {}{}",
+        prov.commit,
+        if prov.dirty { " (dirty)" } else { "" }
+    );
+    println!("host:    {}", prov.host);
+    println!("target:  {}", prov.target);
+    println!("rustc:   {}", prov.rustc_version);
+    println!("backends: {}", backends::compiled().join(", "));
+}
@@ -0,0 +1,33 @@
+use std::io;
+use std::path::Path;
+
+// Build-time facts stamped into env!() by build.rs's stamp_provenance().
+pub struct BuildProvenance {
+    pub commit: &'static str,
+    pub dirty: bool,
+    pub host: &'static str,
+    pub target: &'static str,
+    pub rustc_version: &'static str,
+}
+
+impl BuildProvenance {
+    pub fn current() -> Self {
+        BuildProvenance {
+            commit: env!("ZISK_BUILD_COMMIT"),
+            dirty: env!("ZISK_BUILD_DIRTY") == "true",
+            host: env!("ZISK_BUILD_HOST"),
+            target: env!("ZISK_BUILD_TARGET"),
+            rustc_version: env!("ZISK_BUILD_RUSTC"),
+        }
+    }
+
+    // Same `key\tvalue` format as build/inputs.manifest, so a proof can be
+    // traced back to the exact build that produced it.
+    pub fn write_header(&self, proof_dir: &Path) -> io::Result<()> {
+        let header = format!(
+            "commit\t{}\ndirty\t{}\nhost\t{}\ntarget\t{}\nrustc\t{}\n",
+            self.commit, self.dirty, self.host, self.target, self.rustc_version
+        );
+        std::fs::write(proof_dir.join("proof.provenance"), header)
+    }
+}
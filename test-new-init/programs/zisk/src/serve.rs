@@ -0,0 +1,42 @@
+include!(concat!(env!("OUT_DIR"), "/generated.rs"));
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+
+// Serves the dashboard embedded by build.rs's bundle_dashboard() over plain
+// HTTP, so `zisk serve` needs no external frontend assets on disk.
+pub fn run(port: u16) {
+    let resources = generate();
+    let listener = TcpListener::bind(("127.0.0.1", port)).expect("failed to bind");
+    println!("zisk serve: dashboard on http://127.0.0.1:{port}");
+
+    for stream in listener.incoming() {
+        let mut stream = match stream {
+            Ok(stream) => stream,
+            Err(_) => continue,
+        };
+
+        let mut buf = [0u8; 1024];
+        let read = stream.read(&mut buf).unwrap_or(0);
+        let request = String::from_utf8_lossy(&buf[..read]);
+        let path = request
+            .lines()
+            .next()
+            .and_then(|line| line.split_whitespace().nth(1))
+            .unwrap_or("/");
+        let key = match path.trim_start_matches('/') {
+            "" => "index.html",
+            key => key,
+        };
+
+        match resources.get(key) {
+            Some(resource) => {
+                let _ = stream.write_all(b"HTTP/1.1 200 OK\r\n\r\n");
+                let _ = stream.write_all(resource.data);
+            }
+            None => {
+                let _ = stream.write_all(b"HTTP/1.1 404 Not Found\r\n\r\n");
+            }
+        }
+    }
+}